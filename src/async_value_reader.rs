@@ -0,0 +1,251 @@
+//! Async extraction of data from 2bit files (feature = "tokio")
+//!
+//! Mirrors [`crate::value_reader::ValueReader`] but is generic over
+//! `tokio::io::AsyncRead + AsyncSeek` instead of the blocking `std::io`
+//! traits, so 2bit files can be parsed directly from network streams,
+//! object storage, or other async sources without blocking a thread.
+
+use std::io::SeekFrom;
+use std::mem::size_of;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+
+use crate::block::Block;
+use crate::error::{Error, Result};
+use crate::types::{Field, FileIndex};
+use crate::value_reader::{slice_to_field, FIELD_SIZE, LONG_FIELD_SIZE};
+use crate::{REV_SIGNATURE, SIGNATURE};
+
+pub trait AsyncReader: AsyncRead + AsyncSeek + Unpin {}
+
+impl<T: AsyncRead + AsyncSeek + Unpin> AsyncReader for T {}
+
+/// Async counterpart of [`crate::value_reader::ValueReader`].
+///
+/// This reads all types of fields except the sequences.
+pub struct AsyncValueReader<R: AsyncReader> {
+    reader: R,
+    twobit_version: Field,
+    swap_endian: bool,
+}
+
+pub type BoxAsyncValueReader = AsyncValueReader<Box<dyn AsyncReader>>;
+
+impl<R: AsyncReader> AsyncValueReader<R> {
+    pub async fn new(reader: R) -> Result<Self> {
+        let mut result = Self {
+            reader,
+            twobit_version: 0,
+            swap_endian: false,
+        };
+        let signature = result.field().await?;
+        if signature != SIGNATURE {
+            if signature == REV_SIGNATURE {
+                result.swap_endian = true;
+            } else {
+                return Err(Error::FileFormat(
+                    "File does not start with 2bit signature".to_string(),
+                ));
+            }
+        }
+        let version = result.field().await?;
+        if version == 0 || version == 1 {
+            result.twobit_version = version;
+            Ok(result)
+        } else {
+            Err(Error::UnsupportedVersion(format!(
+                "Unsupported 2bit version: {version}"
+            )))
+        }
+    }
+
+    /// Box the reader (useful for type erasure if using multiple reader types).
+    pub fn boxed(self) -> BoxAsyncValueReader
+    where
+        R: 'static,
+    {
+        AsyncValueReader {
+            reader: Box::new(self.reader),
+            twobit_version: self.twobit_version,
+            swap_endian: self.swap_endian,
+        }
+    }
+
+    fn offset_size(&self) -> usize {
+        if self.twobit_version == 0 {
+            FIELD_SIZE
+        } else {
+            LONG_FIELD_SIZE
+        }
+    }
+
+    pub async fn seek(&mut self, pos: SeekFrom) -> Result<FileIndex> {
+        match self.reader.seek(pos).await {
+            Ok(v) => Ok(v as FileIndex),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn seek_start(&mut self) -> Result<()> {
+        self.seek(SeekFrom::Start(2 * size_of::<Field>() as u64))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn tell(&mut self) -> Result<FileIndex> {
+        match self.reader.seek(SeekFrom::Current(0)).await {
+            Ok(v) => Ok(v as FileIndex),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn stream_len(&mut self) -> Result<u64> {
+        // borrowed from unstable Seek method in stdlib
+        let old_pos = self.reader.seek(SeekFrom::Current(0)).await?;
+        let len = self.reader.seek(SeekFrom::End(0)).await?;
+        // Avoid seeking a third time when we were already at the end of the
+        // stream. The branch is usually way cheaper than a seek operation.
+        if old_pos != len {
+            self.reader.seek(SeekFrom::Start(old_pos)).await?;
+        }
+        Ok(len)
+    }
+
+    pub async fn byte(&mut self) -> Result<u8> {
+        let mut byte_slice: [u8; 1] = [0; 1];
+        self.fill_completely(&mut byte_slice).await?;
+        Ok(byte_slice[0])
+    }
+
+    pub async fn field(&mut self) -> Result<Field> {
+        let mut field: [u8; FIELD_SIZE] = [0; FIELD_SIZE];
+        self.fill_completely(&mut field).await?;
+        Ok(slice_to_field(field, self.swap_endian) as Field)
+    }
+
+    /// Read an offset field, sized according to the file's version: 4 bytes
+    /// for the standard format, 8 bytes for the 64-bit "long" variant.
+    pub async fn offset_field(&mut self) -> Result<FileIndex> {
+        if self.twobit_version == 0 {
+            let mut field: [u8; FIELD_SIZE] = [0; FIELD_SIZE];
+            self.fill_completely(&mut field).await?;
+            Ok(slice_to_field(field, self.swap_endian) as FileIndex)
+        } else {
+            let mut field: [u8; LONG_FIELD_SIZE] = [0; LONG_FIELD_SIZE];
+            self.fill_completely(&mut field).await?;
+            Ok(slice_to_field(field, self.swap_endian) as FileIndex)
+        }
+    }
+
+    pub async fn string(&mut self, length: usize) -> Result<String> {
+        let mut buf = vec![0_u8; length];
+        self.fill_completely(&mut buf).await?;
+        Ok(String::from_utf8(buf)?)
+    }
+
+    pub async fn blocks(&mut self) -> Result<Vec<Block>> {
+        let num_blocks = self.field().await? as usize;
+        let mut result = Vec::with_capacity(num_blocks);
+        for _ in 0..num_blocks {
+            result.push(Block {
+                start: self.offset_field().await?,
+                length: 0, // will be assigned in the next loop
+            });
+        }
+
+        for block in &mut result {
+            block.length = self.offset_field().await?;
+        }
+        Ok(result)
+    }
+
+    pub async fn skip_blocks(&mut self) -> Result<()> {
+        let num_blocks = self.field().await? as usize;
+        let skip = num_blocks * 2 * self.offset_size();
+        self.reader.seek(SeekFrom::Current(skip as i64)).await?;
+        Ok(())
+    }
+
+    /// Read bytes from the reader until the buffer is completely full
+    async fn fill_completely(&mut self, buf: &mut [u8]) -> Result<()> {
+        self.reader.read_exact(buf).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use tokio::io::ReadBuf;
+
+    use super::*;
+
+    /// In-memory `AsyncRead + AsyncSeek` over a `Vec<u8>`, for tests. Since the
+    /// whole buffer is already resident, every poll completes synchronously by
+    /// delegating to the `std::io` `Cursor` it wraps.
+    struct TestCursor(Cursor<Vec<u8>>);
+
+    impl AsyncRead for TestCursor {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            let n = std::io::Read::read(&mut self.0, buf.initialize_unfilled())?;
+            buf.advance(n);
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl AsyncSeek for TestCursor {
+        fn start_seek(mut self: Pin<&mut Self>, position: SeekFrom) -> std::io::Result<()> {
+            std::io::Seek::seek(&mut self.0, position).map(|_| ())
+        }
+
+        fn poll_complete(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+            Poll::Ready(Ok(self.0.position()))
+        }
+    }
+
+    fn reader(data: Vec<u8>) -> TestCursor {
+        TestCursor(Cursor::new(data))
+    }
+
+    #[tokio::test]
+    async fn test_async_blocks_round_trip() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&SIGNATURE.to_be_bytes());
+        data.extend_from_slice(&0_u32.to_be_bytes());
+        data.extend_from_slice(&2_u32.to_be_bytes()); // num_blocks
+        data.extend_from_slice(&10_u32.to_be_bytes()); // start[0]
+        data.extend_from_slice(&20_u32.to_be_bytes()); // start[1]
+        data.extend_from_slice(&3_u32.to_be_bytes()); // length[0]
+        data.extend_from_slice(&4_u32.to_be_bytes()); // length[1]
+
+        let mut value_reader = AsyncValueReader::new(reader(data)).await.unwrap();
+        let blocks = value_reader.blocks().await.unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].start, 10);
+        assert_eq!(blocks[0].length, 3);
+        assert_eq!(blocks[1].start, 20);
+        assert_eq!(blocks[1].length, 4);
+    }
+
+    #[tokio::test]
+    async fn test_async_skip_blocks_long() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&SIGNATURE.to_be_bytes());
+        data.extend_from_slice(&1_u32.to_be_bytes()); // long-format version
+        data.extend_from_slice(&1_u32.to_be_bytes()); // num_blocks
+        data.extend_from_slice(&10_u64.to_be_bytes());
+        data.extend_from_slice(&20_u64.to_be_bytes());
+        data.extend_from_slice(&99_u32.to_be_bytes()); // sentinel past the table
+
+        let mut value_reader = AsyncValueReader::new(reader(data)).await.unwrap();
+        value_reader.skip_blocks().await.unwrap();
+        assert_eq!(value_reader.field().await.unwrap(), 99);
+    }
+}
@@ -10,7 +10,8 @@ use crate::error::{Error, Result};
 use crate::types::{Field, FileIndex};
 use crate::{REV_SIGNATURE, SIGNATURE};
 
-const FIELD_SIZE: usize = size_of::<Field>();
+pub(crate) const FIELD_SIZE: usize = size_of::<Field>();
+pub(crate) const LONG_FIELD_SIZE: usize = size_of::<u64>();
 
 pub trait Reader: Read + Seek {}
 
@@ -23,6 +24,11 @@ pub struct ValueReader<R: Reader> {
     reader: R,
     twobit_version: Field,
     swap_endian: bool,
+    /// Reused across [`fields`](Self::fields)/[`offset_fields`](Self::offset_fields)
+    /// calls so that the byte buffer batched reads fill only reallocates when
+    /// growing past the largest read seen so far (the returned `Vec` of
+    /// decoded values is still allocated on every call).
+    scratch: Vec<u8>,
 }
 
 pub type BoxValueReader = ValueReader<Box<dyn Reader>>;
@@ -50,12 +56,49 @@ impl ValueReader<Cursor<Vec<u8>>> {
     }
 }
 
+impl ValueReader<SliceReader> {
+    /// Wrap an owned, fully-buffered byte vector for zero-copy field access.
+    ///
+    /// Unlike [`open_and_read`](Self::open_and_read), which drives the generic
+    /// `Read`/`Seek` path over a `Cursor`, this keeps the buffer reachable via
+    /// [`slice`](Self::slice) so that sequence extraction can borrow ranges of
+    /// it directly instead of copying them out.
+    pub fn from_owned_buf(buf: Vec<u8>) -> Result<Self> {
+        Self::new(SliceReader::new(buf))
+    }
+
+    /// Borrow the full backing buffer, e.g. for zero-copy sequence extraction.
+    pub fn slice(&self) -> &[u8] {
+        self.reader.as_slice()
+    }
+
+    /// Read a field at an arbitrary byte offset without moving the cursor,
+    /// reusing the endianity decided once in [`new`](Self::new).
+    pub fn field_at(&self, offset: usize) -> Result<Field> {
+        self.reader.field_at(offset, self.swap_endian)
+    }
+
+    /// Borrow `len` bytes starting at the cursor, advancing it, without copying.
+    pub fn bytes(&mut self, len: usize) -> Result<&[u8]> {
+        // Qualified to avoid resolving to `Read::bytes` (in scope through the
+        // `std::io::Read` import) over the inherent `SliceReader::bytes`.
+        SliceReader::bytes(&mut self.reader, len)
+    }
+
+    /// Borrow `len` bytes starting at the cursor as a `&str`, advancing it,
+    /// without copying.
+    pub fn str(&mut self, len: usize) -> Result<&str> {
+        SliceReader::str(&mut self.reader, len)
+    }
+}
+
 impl<R: Reader> ValueReader<R> {
     pub fn new(reader: R) -> Result<Self> {
         let mut result = Self {
             reader,
             twobit_version: 0,
             swap_endian: false,
+            scratch: Vec::new(),
         };
         let signature = result.field()?;
         if signature != SIGNATURE {
@@ -68,13 +111,24 @@ impl<R: Reader> ValueReader<R> {
             }
         }
         let version = result.field()?;
-        if version == 0 {
+        if version == 0 || version == 1 {
             result.twobit_version = version;
             Ok(result)
         } else {
-            Err(Error::UnsupportedVersion(
-                "Versions larger than 0 are not supported".to_string(),
-            ))
+            Err(Error::UnsupportedVersion(format!(
+                "Unsupported 2bit version: {version}"
+            )))
+        }
+    }
+
+    /// Size in bytes of an offset field for this file's version: 4 bytes for
+    /// the standard format, 8 bytes for the 64-bit "long" variant (version 1)
+    /// used to represent files and genomes exceeding 4 GiB.
+    fn offset_size(&self) -> usize {
+        if self.twobit_version == 0 {
+            FIELD_SIZE
+        } else {
+            LONG_FIELD_SIZE
         }
     }
 
@@ -87,6 +141,7 @@ impl<R: Reader> ValueReader<R> {
             reader: Box::new(self.reader),
             twobit_version: self.twobit_version,
             swap_endian: self.swap_endian,
+            scratch: self.scratch,
         }
     }
 
@@ -123,69 +178,296 @@ impl<R: Reader> ValueReader<R> {
 
     pub fn byte(&mut self) -> Result<u8> {
         let mut byte_slice: [u8; 1] = [0; 1];
-        self.fill_completely(&mut byte_slice)?;
+        fill_completely(&mut self.reader, &mut byte_slice)?;
         Ok(byte_slice[0])
     }
 
     pub fn field(&mut self) -> Result<Field> {
         let mut field: [u8; FIELD_SIZE] = [0; FIELD_SIZE];
-        self.fill_completely(&mut field)?;
-        Ok(slice_to_field(field, self.swap_endian))
+        fill_completely(&mut self.reader, &mut field)?;
+        Ok(slice_to_field(field, self.swap_endian) as Field)
+    }
+
+    /// Read an offset field, sized according to the file's version: 4 bytes
+    /// for the standard format, 8 bytes for the 64-bit "long" variant.
+    pub fn offset_field(&mut self) -> Result<FileIndex> {
+        if self.twobit_version == 0 {
+            let mut field: [u8; FIELD_SIZE] = [0; FIELD_SIZE];
+            fill_completely(&mut self.reader, &mut field)?;
+            Ok(slice_to_field(field, self.swap_endian) as FileIndex)
+        } else {
+            let mut field: [u8; LONG_FIELD_SIZE] = [0; LONG_FIELD_SIZE];
+            fill_completely(&mut self.reader, &mut field)?;
+            Ok(slice_to_field(field, self.swap_endian) as FileIndex)
+        }
+    }
+
+    /// Read the file header's sequence count, immediately following the
+    /// version field, sized according to the file's version (4 bytes
+    /// standard, 8 bytes for the "long" variant) so that file-index tables
+    /// with more than 2^32 entries remain addressable.
+    pub fn sequence_count(&mut self) -> Result<FileIndex> {
+        self.offset_field()
+    }
+
+    /// Read one entry of the file-index table that follows the header: a
+    /// length-prefixed sequence name and its offset into the file. The
+    /// offset is read through [`offset_field`](Self::offset_field), so it is
+    /// 8 bytes wide for the "long" variant, keeping large files addressable.
+    /// Callers read [`sequence_count`](Self::sequence_count) of these.
+    pub fn file_index_entry(&mut self) -> Result<(String, FileIndex)> {
+        let name_len = self.byte()? as usize;
+        let name = self.string(name_len)?;
+        let offset = self.offset_field()?;
+        Ok((name, offset))
     }
 
     pub fn string(&mut self, length: usize) -> Result<String> {
         let mut buf = vec![0_u8; length];
-        self.fill_completely(&mut buf)?;
+        fill_completely(&mut self.reader, &mut buf)?;
         Ok(String::from_utf8(buf)?)
     }
 
+    /// Bulk-read `count` fields in a single fill, avoiding a syscall per
+    /// field. Reuses the reader's scratch buffer across calls, so only a
+    /// fill that grows past the largest one seen so far reallocates.
+    pub fn fields(&mut self, count: usize) -> Result<Vec<Field>> {
+        let n_bytes = count * FIELD_SIZE;
+        self.scratch.clear();
+        self.scratch.resize(n_bytes, 0);
+        fill_completely(&mut self.reader, &mut self.scratch)?;
+        let decode: fn([u8; FIELD_SIZE]) -> Field = if self.swap_endian {
+            Field::from_le_bytes
+        } else {
+            Field::from_be_bytes
+        };
+        Ok(self
+            .scratch
+            .chunks_exact(FIELD_SIZE)
+            .map(|chunk| decode(chunk.try_into().unwrap()))
+            .collect())
+    }
+
+    /// Bulk-read `count` offset fields in a single fill, sized according to
+    /// the file's version (see [`offset_field`](Self::offset_field)). Reuses
+    /// the reader's scratch buffer across calls like [`fields`](Self::fields).
+    pub fn offset_fields(&mut self, count: usize) -> Result<Vec<FileIndex>> {
+        let width = self.offset_size();
+        let swap_endian = self.swap_endian;
+        self.scratch.clear();
+        self.scratch.resize(count * width, 0);
+        fill_completely(&mut self.reader, &mut self.scratch)?;
+        Ok(self
+            .scratch
+            .chunks_exact(width)
+            .map(|chunk| {
+                if width == FIELD_SIZE {
+                    let bytes: [u8; FIELD_SIZE] = chunk.try_into().unwrap();
+                    let field = if swap_endian {
+                        Field::from_le_bytes(bytes)
+                    } else {
+                        Field::from_be_bytes(bytes)
+                    };
+                    field as FileIndex
+                } else {
+                    let bytes: [u8; LONG_FIELD_SIZE] = chunk.try_into().unwrap();
+                    if swap_endian {
+                        u64::from_le_bytes(bytes)
+                    } else {
+                        u64::from_be_bytes(bytes)
+                    }
+                }
+            })
+            .collect())
+    }
+
     pub fn blocks(&mut self) -> Result<Vec<Block>> {
         let num_blocks = self.field()? as usize;
-        let mut result = Vec::with_capacity(num_blocks);
-        for _ in 0..num_blocks {
-            result.push(Block {
-                start: self.field()?,
-                length: 0, // will be assigned in the next loop
-            });
-        }
-
-        for block in &mut result {
-            block.length = self.field()?;
-        }
-        Ok(result)
+        let offsets = self.offset_fields(num_blocks * 2)?;
+        let (starts, lengths) = offsets.split_at(num_blocks);
+        Ok(starts
+            .iter()
+            .zip(lengths)
+            .map(|(&start, &length)| Block { start, length })
+            .collect())
     }
 
     pub fn skip_blocks(&mut self) -> Result<()> {
         let num_blocks = self.field()? as usize;
-        let skip = num_blocks * 2 * size_of::<Field>();
+        let skip = num_blocks * 2 * self.offset_size();
         self.reader.seek(SeekFrom::Current(skip as i64))?;
         Ok(())
     }
 
-    /// Read bytes from the reader until the buffer is completely full
-    fn fill_completely(&mut self, buf: &mut [u8]) -> Result<()> {
-        let n_bytes = buf.len();
-        let mut bytes_read = 0;
-        while bytes_read < n_bytes {
-            // our reader doesn't guarantee that it's always reading enough bytes at once
-            bytes_read += self.reader.read(&mut buf[bytes_read..])?;
+    /// Walk the `len` packed bytes at `[start, start + len)` from the end
+    /// toward the start, one `seek` + [`byte`](Self::byte) at a time.
+    ///
+    /// This lets callers build reverse-complement (minus-strand) extraction
+    /// on top without first materializing the forward sequence, at the cost
+    /// of one seek per byte.
+    ///
+    /// Because the return type is a plain `Iterator<Item = u8>`, a seek or
+    /// read failure partway through the range cannot be surfaced as an
+    /// error: [`ReverseBytes::next`] treats it the same as having reached
+    /// `start`, so the iterator simply ends early and the caller gets a
+    /// silently truncated (not corrupted) result. Prefer this only where
+    /// that truncation-on-error tradeoff is acceptable.
+    pub fn read_bytes_rev(&mut self, start: FileIndex, len: usize) -> ReverseBytes<'_, R> {
+        ReverseBytes {
+            reader: self,
+            pos: start + len as FileIndex,
+            remaining: len,
         }
-        assert_eq!(bytes_read, n_bytes);
-        Ok(())
     }
 }
 
-fn slice_to_field(slice: [u8; FIELD_SIZE], swap_endian: bool) -> Field {
-    let mut result = 0;
+/// Read bytes from `reader` until `buf` is completely full.
+///
+/// Free function rather than a method so that callers filling into
+/// `self.scratch` (see [`ValueReader::fields`]) can pass `&mut self.reader`
+/// without a second, conflicting borrow of `self`.
+fn fill_completely<R: Reader>(reader: &mut R, buf: &mut [u8]) -> Result<()> {
+    let n_bytes = buf.len();
+    let mut bytes_read = 0;
+    while bytes_read < n_bytes {
+        // our reader doesn't guarantee that it's always reading enough bytes at once
+        bytes_read += reader.read(&mut buf[bytes_read..])?;
+    }
+    assert_eq!(bytes_read, n_bytes);
+    Ok(())
+}
+
+/// Iterator returned by [`ValueReader::read_bytes_rev`], yielding packed
+/// bytes from the end of the requested range toward its start.
+///
+/// Each step seeks to the next byte and reads it, so this is bounded-memory
+/// but trades throughput for not requiring the whole range to be buffered.
+pub struct ReverseBytes<'a, R: Reader> {
+    reader: &'a mut ValueReader<R>,
+    pos: FileIndex,
+    remaining: usize,
+}
+
+impl<'a, R: Reader> Iterator for ReverseBytes<'a, R> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.pos -= 1;
+        self.remaining -= 1;
+        let byte = self
+            .reader
+            .seek(SeekFrom::Start(self.pos))
+            .and_then(|_| self.reader.byte());
+        if byte.is_err() {
+            // end the iteration for good, as if `start` had been reached
+            self.remaining = 0;
+        }
+        byte.ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, R: Reader> ExactSizeIterator for ReverseBytes<'a, R> {}
+
+/// An owned in-memory buffer paired with a cursor, implementing [`Reader`]
+/// like [`Cursor`] but additionally exposing a borrowing API so that fields
+/// and sequence bytes can be sliced out of the backing buffer without
+/// allocating. Suitable for fully-buffered access where the whole file has
+/// been read into a `Vec<u8>`; the buffer is owned by this reader and is not
+/// shared with other readers.
+pub struct SliceReader {
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl SliceReader {
+    fn new(buf: Vec<u8>) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Borrow the full backing buffer.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Read a field at an arbitrary byte offset without moving the cursor.
+    ///
+    /// Takes `swap_endian` explicitly since `SliceReader` itself has no
+    /// notion of endianity; [`ValueReader<SliceReader>`] bakes it in once at
+    /// construction and exposes a callers-don't-pass-it-each-time wrapper.
+    fn field_at(&self, offset: usize, swap_endian: bool) -> Result<Field> {
+        let slice = self
+            .buf
+            .get(offset..offset + FIELD_SIZE)
+            .ok_or_else(|| Error::FileFormat("field offset out of bounds".to_string()))?;
+        let mut field = [0_u8; FIELD_SIZE];
+        field.copy_from_slice(slice);
+        Ok(slice_to_field(field, swap_endian) as Field)
+    }
+
+    /// Borrow `len` bytes starting at the cursor, advancing it.
+    fn bytes(&mut self, len: usize) -> Result<&[u8]> {
+        let end = self.pos + len;
+        let slice = self
+            .buf
+            .get(self.pos..end)
+            .ok_or_else(|| Error::FileFormat("read past end of buffer".to_string()))?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    /// Borrow `len` bytes starting at the cursor as a `&str`, advancing it.
+    fn str(&mut self, len: usize) -> Result<&str> {
+        let bytes = self.bytes(len)?;
+        std::str::from_utf8(bytes)
+            .map_err(|e| Error::FileFormat(format!("invalid utf-8 in buffer: {e}")))
+    }
+}
+
+impl Read for SliceReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = (self.buf.len() - self.pos).min(buf.len());
+        buf[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl Seek for SliceReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(p) => p as i64,
+            SeekFrom::End(p) => self.buf.len() as i64 + p,
+            SeekFrom::Current(p) => self.pos as i64 + p,
+        };
+        if new_pos < 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            ));
+        }
+        self.pos = new_pos as usize;
+        Ok(self.pos as u64)
+    }
+}
+
+pub(crate) fn slice_to_field<const N: usize>(slice: [u8; N], swap_endian: bool) -> u64 {
+    let mut result: u64 = 0;
     if swap_endian {
         for byte in slice.iter().rev().copied() {
             result <<= 8;
-            result += Field::from(byte);
+            result += u64::from(byte);
         }
     } else {
         for byte in slice {
             result <<= 8;
-            result += Field::from(byte);
+            result += u64::from(byte);
         }
     }
     result
@@ -210,4 +492,138 @@ mod tests {
         let slice: [u8; 4] = [0, 2, 3, 4];
         assert_eq!(slice_to_field(slice, false), 2 * 65536 + 3 * 256 + 4);
     }
+
+    #[test]
+    fn test_slice_to_field_long() {
+        let slice: [u8; 8] = [0, 0, 0, 0, 0, 2, 3, 4];
+        assert_eq!(slice_to_field(slice, false), 2 * 65536 + 3 * 256 + 4);
+
+        let slice: [u8; 8] = [4, 3, 2, 0, 0, 0, 0, 0];
+        assert_eq!(slice_to_field(slice, true), 2 * 65536 + 3 * 256 + 4);
+    }
+
+    #[test]
+    fn test_from_owned_buf_zero_copy_round_trip() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&SIGNATURE.to_be_bytes());
+        data.extend_from_slice(&0_u32.to_be_bytes());
+        data.extend_from_slice(b"chr1");
+        let field_offset = data.len();
+        data.extend_from_slice(&42_u32.to_be_bytes());
+
+        let mut reader = ValueReader::from_owned_buf(data.clone()).unwrap();
+        assert_eq!(reader.slice(), data.as_slice());
+        assert_eq!(reader.field_at(field_offset).unwrap(), 42);
+        // the cursor is untouched by `field_at`'s random access
+        assert_eq!(reader.str(4).unwrap(), "chr1");
+        assert_eq!(reader.bytes(4).unwrap(), &data[field_offset..field_offset + 4]);
+    }
+
+    #[test]
+    fn test_sequence_count_and_file_index_entry_long() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&SIGNATURE.to_be_bytes());
+        data.extend_from_slice(&1_u32.to_be_bytes()); // long-format version
+        data.extend_from_slice(&2_u64.to_be_bytes()); // sequence count, 8 bytes
+        data.push(4); // name length
+        data.extend_from_slice(b"chr1");
+        data.extend_from_slice(&(u32::MAX as u64 + 1).to_be_bytes()); // offset > 4 GiB
+
+        let mut reader = ValueReader::from_buf(data).unwrap();
+        assert_eq!(reader.sequence_count().unwrap(), 2);
+        let (name, offset) = reader.file_index_entry().unwrap();
+        assert_eq!(name, "chr1");
+        assert_eq!(offset, u32::MAX as u64 + 1);
+    }
+
+    #[test]
+    fn test_fields_bulk_matches_one_at_a_time() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&SIGNATURE.to_be_bytes());
+        data.extend_from_slice(&0_u32.to_be_bytes());
+        for value in [1_u32, 2, 3, 4] {
+            data.extend_from_slice(&value.to_be_bytes());
+        }
+
+        let mut reader = ValueReader::from_buf(data.clone()).unwrap();
+        let bulk = reader.fields(4).unwrap();
+
+        let mut reader = ValueReader::from_buf(data).unwrap();
+        let one_at_a_time: Vec<Field> = (0..4).map(|_| reader.field().unwrap()).collect();
+
+        assert_eq!(bulk, one_at_a_time);
+        assert_eq!(bulk, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_blocks_round_trip_standard() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&SIGNATURE.to_be_bytes());
+        data.extend_from_slice(&0_u32.to_be_bytes());
+        data.extend_from_slice(&2_u32.to_be_bytes()); // num_blocks
+        data.extend_from_slice(&10_u32.to_be_bytes()); // start[0]
+        data.extend_from_slice(&20_u32.to_be_bytes()); // start[1]
+        data.extend_from_slice(&3_u32.to_be_bytes()); // length[0]
+        data.extend_from_slice(&4_u32.to_be_bytes()); // length[1]
+        data.extend_from_slice(&99_u32.to_be_bytes()); // sentinel past the table
+
+        let mut reader = ValueReader::from_buf(data).unwrap();
+        let blocks = reader.blocks().unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].start, 10);
+        assert_eq!(blocks[0].length, 3);
+        assert_eq!(blocks[1].start, 20);
+        assert_eq!(blocks[1].length, 4);
+        assert_eq!(reader.field().unwrap(), 99);
+    }
+
+    #[test]
+    fn test_blocks_round_trip_long() {
+        let big_offset = u32::MAX as u64 + 20;
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&SIGNATURE.to_be_bytes());
+        data.extend_from_slice(&1_u32.to_be_bytes()); // long-format version
+        data.extend_from_slice(&2_u32.to_be_bytes()); // num_blocks stays 4 bytes
+        data.extend_from_slice(&10_u64.to_be_bytes()); // start[0], 8 bytes
+        data.extend_from_slice(&big_offset.to_be_bytes()); // start[1], beyond 4 GiB
+        data.extend_from_slice(&3_u64.to_be_bytes()); // length[0]
+        data.extend_from_slice(&4_u64.to_be_bytes()); // length[1]
+
+        let mut reader = ValueReader::from_buf(data).unwrap();
+        let blocks = reader.blocks().unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].start, 10);
+        assert_eq!(blocks[0].length, 3);
+        assert_eq!(blocks[1].start, big_offset);
+        assert_eq!(blocks[1].length, 4);
+    }
+
+    #[test]
+    fn test_skip_blocks_long() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&SIGNATURE.to_be_bytes());
+        data.extend_from_slice(&1_u32.to_be_bytes()); // long-format version
+        data.extend_from_slice(&1_u32.to_be_bytes()); // num_blocks
+        data.extend_from_slice(&10_u64.to_be_bytes());
+        data.extend_from_slice(&20_u64.to_be_bytes());
+        data.extend_from_slice(&99_u32.to_be_bytes()); // sentinel past the table
+
+        let mut reader = ValueReader::from_buf(data).unwrap();
+        reader.skip_blocks().unwrap();
+        assert_eq!(reader.field().unwrap(), 99);
+    }
+
+    #[test]
+    fn test_read_bytes_rev() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&SIGNATURE.to_be_bytes());
+        data.extend_from_slice(&0_u32.to_be_bytes());
+        let payload_start = data.len() as FileIndex;
+        data.extend_from_slice(&[0x11, 0x22, 0x33, 0x44, 0x55]);
+
+        let mut reader = ValueReader::from_buf(data).unwrap();
+        let rev: Vec<u8> = reader.read_bytes_rev(payload_start, 5).collect();
+        assert_eq!(rev, vec![0x55, 0x44, 0x33, 0x22, 0x11]);
+    }
 }